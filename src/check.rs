@@ -0,0 +1,201 @@
+use std::ffi::OsStr;
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::redirect::Policy;
+use tokio::sync::{Semaphore, SemaphorePermit};
+use tracing::{error, warn};
+
+use crate::link::Link;
+use crate::link_to_toml;
+
+/// A Chrome-ish user-agent so that servers that special-case bots still answer us.
+const USER_AGENT: &str =
+    "Mozilla/5.0 (X11; Linux x86_64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0 Safari/537.36";
+
+/// How long a single request is allowed to take before we call it a `Timeout`.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Hands out permits from a shared [`Semaphore`] so that at most N requests are
+/// in flight at once. Cloning the handle is cheap (it just bumps the `Arc`), so
+/// every spawned check task gets its own handle off which to `acquire`.
+#[derive(Clone)]
+struct Dispenser {
+    sem: Arc<Semaphore>,
+}
+
+impl Dispenser {
+    fn new(permits: usize) -> Dispenser {
+        Dispenser {
+            sem: Arc::new(Semaphore::new(permits.max(1))),
+        }
+    }
+
+    /// Block until a slot frees up, then hold it for the lifetime of the returned permit.
+    async fn acquire(&self) -> SemaphorePermit<'_> {
+        self.sem
+            .acquire()
+            .await
+            .expect("check semaphore closed unexpectedly")
+    }
+}
+
+/// The outcome of checking a single bookmark's URL.
+#[derive(Debug)]
+enum CheckOutcome {
+    /// The server answered with a 2xx.
+    Ok,
+    /// A non-2xx status. `location` carries the `Location` header for 3xx "moved" responses.
+    HttpError {
+        status: u16,
+        location: Option<String>,
+    },
+    /// The request did not complete within [`REQUEST_TIMEOUT`].
+    Timeout,
+    /// We never got a response (DNS failure, refused connection, TLS error, ...).
+    ConnectionError { detail: String },
+}
+
+/// A bookmark that did not come back clean, paired with why.
+struct BrokenLink {
+    title: String,
+    url: String,
+    outcome: CheckOutcome,
+}
+
+/// Check one URL. Never panics: every failure mode is folded into [`CheckOutcome`].
+async fn check_one(client: &reqwest::Client, url: &str) -> CheckOutcome {
+    match client.get(url).send().await {
+        Ok(resp) => {
+            let status = resp.status();
+            if status.is_success() {
+                CheckOutcome::Ok
+            } else {
+                let location = resp
+                    .headers()
+                    .get(reqwest::header::LOCATION)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string);
+                CheckOutcome::HttpError {
+                    status: status.as_u16(),
+                    location,
+                }
+            }
+        }
+        Err(err) => {
+            if err.is_timeout() {
+                CheckOutcome::Timeout
+            } else {
+                CheckOutcome::ConnectionError {
+                    detail: err.to_string(),
+                }
+            }
+        }
+    }
+}
+
+/// Walk `dir`, deserialize every `*.toml` into a [`Link`], and return each one
+/// alongside its path so the caller can rewrite it after a successful check.
+fn collect_links(dir: &str) -> std::io::Result<Vec<(std::path::PathBuf, Link)>> {
+    let entries = fs::read_dir(dir)?;
+    let mut links = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
+            let content = match fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(err) => {
+                    warn!("Skipping {}: unable to read ({})", path.display(), err);
+                    continue;
+                }
+            };
+            match toml::from_str::<Link>(&content) {
+                Ok(link) => links.push((path, link)),
+                Err(err) => warn!("Skipping {}: unable to parse ({})", path.display(), err),
+            }
+        }
+    }
+    Ok(links)
+}
+
+/// Verify every bookmark in `dir` with at most `concurrency` requests in flight.
+///
+/// On a successful 2xx the entry's `accessed` field is bumped to today and its
+/// own file is rewritten in place with [`link_to_toml`]. Broken or moved links are collected and
+/// reported at the end; a single bad URL never aborts the run.
+pub async fn check_entries(dir: &str, concurrency: usize) -> std::io::Result<()> {
+    let links = collect_links(dir)?;
+    println!("Checking {} bookmark(s)...", links.len());
+
+    let client = reqwest::Client::builder()
+        .redirect(Policy::none())
+        .user_agent(USER_AGENT)
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .expect("Unable to build reqwest client");
+    let dispenser = Dispenser::new(concurrency);
+
+    let mut tasks = Vec::with_capacity(links.len());
+    for (path, link) in links {
+        let client = client.clone();
+        let dispenser = dispenser.clone();
+        tasks.push(tokio::spawn(async move {
+            let _permit = dispenser.acquire().await;
+            let outcome = check_one(&client, &link.link).await;
+            if matches!(outcome, CheckOutcome::Ok) {
+                let mut refreshed = link.clone();
+                refreshed.accessed = chrono::Local::now().date_naive();
+                if let Err(err) = fs::write(&path, link_to_toml(&refreshed)) {
+                    warn!("Unable to refresh {}: {}", path.display(), err);
+                }
+                None
+            } else {
+                Some(BrokenLink {
+                    title: link.title,
+                    url: link.link,
+                    outcome,
+                })
+            }
+        }));
+    }
+
+    let mut broken = Vec::new();
+    for task in tasks {
+        match task.await {
+            Ok(Some(b)) => broken.push(b),
+            Ok(None) => {}
+            Err(err) => error!("A check task panicked: {}", err),
+        }
+    }
+
+    report(&broken);
+    Ok(())
+}
+
+/// Print a summary of every link that did not return a clean 2xx.
+fn report(broken: &[BrokenLink]) {
+    if broken.is_empty() {
+        println!("All bookmarks resolved successfully.");
+        return;
+    }
+    println!("\n{} broken or moved bookmark(s):", broken.len());
+    for b in broken {
+        match &b.outcome {
+            CheckOutcome::HttpError {
+                status,
+                location: Some(loc),
+            } => println!("\t[{}] {} -> moved to {} ({})", status, b.title, loc, b.url),
+            CheckOutcome::HttpError {
+                status,
+                location: None,
+            } => println!("\t[{}] {} ({})", status, b.title, b.url),
+            CheckOutcome::Timeout => println!("\t[timeout] {} ({})", b.title, b.url),
+            CheckOutcome::ConnectionError { detail } => {
+                println!("\t[connection] {} ({}): {}", b.title, b.url, detail)
+            }
+            CheckOutcome::Ok => {}
+        }
+    }
+}
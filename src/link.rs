@@ -11,6 +11,7 @@ use serde::{Deserialize, Serialize};
 /// * `added` - The date (in `YYYY-MM-DD`) the resource was added to the database.
 /// * `accessed` - The date (in `YYYY-MM-DD`) the resource was last accessed.
 /// * `tags` - A set of tags associated with the resource.
+/// * `draft` - Whether the resource is an unfinished draft, hidden from taxonomy and site output.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct Link {
     pub title: String,
@@ -24,6 +25,9 @@ pub struct Link {
     // #[serde(with = "ts_seconds_option")]
     // pub accessed: toml_datetime::Date,
     pub tags: Option<HashSet<String>>,
+
+    #[serde(default)]
+    pub draft: bool,
 }
 
 impl Link {
@@ -34,6 +38,7 @@ impl Link {
         added: &str,
         accessed: &str,
         tags: &Option<HashSet<String>>,
+        draft: bool,
     ) -> Link {
         let title = title.into();
         let link = link.into();
@@ -76,8 +81,16 @@ impl Link {
             added,
             accessed,
             tags,
+            draft,
         }
     }
+
+    /// Whether this entry should be visible in taxonomy and static-site output.
+    /// A draft, or an entry whose `added` date is still in the future
+    /// (scheduled), is treated as hidden.
+    pub fn is_published(&self) -> bool {
+        !self.draft && self.added <= chrono::Local::now().date_naive()
+    }
 }
 
 /// Link struct specified to resource.json format.
@@ -90,32 +103,26 @@ pub struct JsonLink {
     pub year: i32,
     pub tags: Option<HashSet<String>>,
     pub free: bool,
+    #[serde(default)]
+    pub draft: bool,
 }
 
-impl JsonLink {
-    pub fn new(
-        title: &str,
-        url: &str,
-        description: &str,
-        category: &str,
-        year: i32,
-        tags: &Option<HashSet<String>>,
-        free: bool,
-    ) -> JsonLink {
-        let title = title.into();
-        let url = url.into();
-        let description = description.into();
-        let category = category.into();
-        let tags = tags.clone();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        JsonLink {
-            title,
-            url,
-            description,
-            category,
-            year,
-            tags,
-            free,
-        }
+    #[test]
+    fn draft_and_future_entries_are_unpublished() {
+        let today = chrono::Local::now().date_naive();
+        let tomorrow = (today + chrono::Duration::days(1)).to_string();
+
+        let published = Link::new("t", "u", "d", "", "", &None, false);
+        assert!(published.is_published());
+
+        let draft = Link::new("t", "u", "d", "", "", &None, true);
+        assert!(!draft.is_published());
+
+        let scheduled = Link::new("t", "u", "d", &tomorrow, "", &None, false);
+        assert!(!scheduled.is_published());
     }
 }
@@ -1,19 +1,21 @@
 use chrono::Datelike;
-use chrono::NaiveDate;
+use clap::{Parser, Subcommand, ValueEnum};
 use scraper::Html;
-use serde::{Deserialize, Serialize};
 use serde_json::Result;
+use tracing::{info, warn};
+use tracing_subscriber::filter::LevelFilter;
 use unidecode::unidecode;
 
 use std::collections::HashSet;
-use std::env;
-use std::ffi::OsStr;
 use std::fs;
 use std::fs::File;
 use std::io::BufReader;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
+mod check;
 mod link;
+mod markdown;
+mod render;
 use link::JsonLink;
 use link::Link;
 pub mod validate;
@@ -75,12 +77,12 @@ fn prompt() -> Link {
         )
     };
 
-    Link::new(&title, &link, &desc, &added, &accessed, &tags)
+    Link::new(&title, &link, &desc, &added, &accessed, &tags, false)
 }
 
-/// Output the link's info to a TOML file.
-fn output(lnk: Link, dir: &str) {
-    let safe_title = unidecode(&lnk.title);
+/// Turn a link title into a filesystem-safe slug (also used to name generated HTML files).
+pub(crate) fn slugify(title: &str) -> String {
+    let safe_title = unidecode(title);
     // TODO ROFL - condense by using iterator with a list of invalid chars.
     let safe_title = safe_title.replace('/', "_");
     let safe_title = safe_title.replace('\\', "_");
@@ -105,25 +107,36 @@ fn output(lnk: Link, dir: &str) {
     let safe_title = safe_title.replace("'", "_");
     let safe_title = safe_title.replace(".", "_");
     let safe_title = safe_title.replace("__", "_");
-    let safe_title = safe_title.to_lowercase();
+    safe_title.to_lowercase()
+}
 
+/// Serialize a link to the TOML representation written out to disk.
+pub(crate) fn link_to_toml(lnk: &Link) -> String {
     let mut text: String = format!(
         "title = \"{title}\"
 link = \"{link}\"
 desc = \"{desc}\"
 added = \"{added}\"
 accessed = \"{accessed}\"
+draft = {draft}
 ",
         title = lnk.title,
         link = lnk.link,
         desc = lnk.desc,
         added = lnk.added,
         accessed = lnk.accessed,
+        draft = lnk.draft,
     );
     if let Some(tags) = &lnk.tags {
         text += &format!("tags = [{}]", format_tags(tags));
     }
-    // print!("Got: {}", text);
+    text
+}
+
+/// Output the link's info to a TOML file named after its slugified title.
+fn output(lnk: Link, dir: &str) {
+    let safe_title = slugify(&lnk.title);
+    let text = link_to_toml(&lnk);
     fs::write(format!("{}/{}.toml", dir, safe_title), text).expect("Unable to write file");
 }
 
@@ -132,7 +145,7 @@ fn read_links_from_json(file_path: &str) -> Result<Vec<JsonLink>> {
     let file = File::open(file_path).expect("Unable to open file_path JSON");
     let reader = BufReader::new(file);
     let links: Vec<JsonLink> = serde_json::from_reader(reader).expect("from_reader failed.");
-    print!("{} links found.", links.len());
+    info!("{} links found.", links.len());
     Ok(links)
 }
 
@@ -151,46 +164,166 @@ fn output_from_json(links: Vec<JsonLink>, dir: &str) {
             &added.to_string(),
             &accessed.to_string(),
             &tags,
+            link.draft,
         );
         output(lnk, dir);
     }
 }
 
-fn main() -> std::io::Result<()> {
-    let toml_directory = "/toml";
-    let cwd: PathBuf = env::current_dir()?;
-    let cwd: String = cwd
-        .to_str()
-        .expect("Unable to convert working path's PathBuf to &str.")
-        .to_string();
-    let toml_path = format!("{}{}", cwd, toml_directory);
-    if fs::metadata(toml_path.clone()).is_err() {
-        fs::create_dir(toml_path.clone()).expect("Unable to create directory");
-    } else {
-        println!("Found existing directory at {}.", &toml_path)
+/// Serialize every `Link` in `dir` back out as a JSON array on stdout.
+fn export(dir: &str) -> std::io::Result<()> {
+    let entries = fs::read_dir(dir)?;
+    let mut links: Vec<Link> = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_file() && path.extension().and_then(std::ffi::OsStr::to_str) == Some("toml") {
+            let content = fs::read_to_string(&path)?;
+            match toml::from_str::<Link>(&content) {
+                Ok(link) => links.push(link),
+                Err(err) => warn!("Skipping {}: unable to parse ({})", path.display(), err),
+            }
+        }
     }
+    let json = serde_json::to_string_pretty(&links).expect("Unable to serialize links to JSON");
+    println!("{}", json);
+    Ok(())
+}
 
-    let result = validate::validate_entries(&toml_path);
-    match (result) {
-        Ok(()) => println!("No errors found."),
-        _ => println!("{:#?} errors found.", result),
+/// Ensure the configured TOML directory exists, creating it if necessary.
+fn ensure_toml_dir(dir: &Path) -> std::io::Result<()> {
+    if fs::metadata(dir).is_err() {
+        fs::create_dir_all(dir)?;
+    } else {
+        info!("Found existing directory at {}.", dir.display());
     }
     Ok(())
+}
 
-    // loop {
-    //     let lnk = prompt();
-    //     output(lnk, &toml_path);
-    //     // print!();
-    //     // let ans =
-    //     let ans = inquire::Text::new(
-    //         "Would you like to add another link? ((N)o/(y)es or any other input): ",
-    //     )
-    //     .prompt()
-    //     .expect("An error happened when asking if you'd like to continue");
-    //     if ans.to_lowercase() == "n" {
-    //         break;
-    //     }
-    // }
-
-    // Ok(())
+/// Verbosity of the `tracing` subscriber, wired up from `--log-level`.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warning,
+    Error,
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> LevelFilter {
+        match level {
+            LogLevel::Trace => LevelFilter::TRACE,
+            LogLevel::Debug => LevelFilter::DEBUG,
+            LogLevel::Info => LevelFilter::INFO,
+            LogLevel::Warning => LevelFilter::WARN,
+            LogLevel::Error => LevelFilter::ERROR,
+        }
+    }
+}
+
+/// A CLI to build and maintain a directory of bookmark TOML files.
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+struct Cli {
+    /// Directory holding the bookmark TOML files.
+    #[arg(long, global = true, default_value = "toml")]
+    toml_dir: PathBuf,
+
+    /// Log verbosity.
+    #[arg(long, value_enum, global = true, default_value_t = LogLevel::Info)]
+    log_level: LogLevel,
+
+    #[command(subcommand)]
+    command: MainCommand,
+}
+
+#[derive(Subcommand, Debug)]
+enum MainCommand {
+    /// Validate every entry in a directory against the `Link` schema.
+    Validate {
+        /// Directory to validate (defaults to `--toml-dir`).
+        dir: Option<PathBuf>,
+    },
+    /// Interactively prompt for new links and write them out.
+    Add,
+    /// Import links from a resource JSON file.
+    Import {
+        /// Path to the JSON file of links.
+        file: PathBuf,
+    },
+    /// Import links from a directory of Markdown files with TOML front matter.
+    ImportMd {
+        /// Directory of `*.md` notes files to import.
+        dir: PathBuf,
+    },
+    /// Export the collection as a JSON array on stdout.
+    Export,
+    /// Check every bookmark for dead or moved links.
+    Check {
+        /// Maximum number of requests in flight at once.
+        #[arg(long, default_value_t = 8)]
+        concurrency: usize,
+    },
+    /// Render the collection as a browsable static site with a search index.
+    Render {
+        /// Directory to write the generated site into.
+        #[arg(long, default_value = "site")]
+        out: PathBuf,
+    },
+}
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let cli = Cli::parse();
+    tracing_subscriber::fmt()
+        .with_max_level(LevelFilter::from(cli.log_level))
+        .init();
+
+    let toml_path = cli.toml_dir.to_string_lossy().into_owned();
+
+    match cli.command {
+        MainCommand::Validate { dir } => {
+            let dir = dir.unwrap_or(cli.toml_dir.clone());
+            let dir = dir.to_string_lossy();
+            validate::validate_entries(&dir)?;
+            info!("No errors found.");
+        }
+        MainCommand::Add => {
+            ensure_toml_dir(&cli.toml_dir)?;
+            loop {
+                let lnk = prompt();
+                output(lnk, &toml_path);
+                let ans = inquire::Text::new(
+                    "Would you like to add another link? ((N)o/(y)es or any other input): ",
+                )
+                .prompt()
+                .expect("An error happened when asking if you'd like to continue");
+                if ans.to_lowercase() == "n" {
+                    break;
+                }
+            }
+        }
+        MainCommand::Import { file } => {
+            ensure_toml_dir(&cli.toml_dir)?;
+            let links = read_links_from_json(&file.to_string_lossy())
+                .expect("Unable to read links from JSON");
+            output_from_json(links, &toml_path);
+        }
+        MainCommand::ImportMd { dir } => {
+            ensure_toml_dir(&cli.toml_dir)?;
+            let links = markdown::read_links_from_markdown(&dir.to_string_lossy())?;
+            for link in links {
+                output(link, &toml_path);
+            }
+        }
+        MainCommand::Export => export(&toml_path)?,
+        MainCommand::Check { concurrency } => {
+            check::check_entries(&toml_path, concurrency).await?;
+        }
+        MainCommand::Render { out } => {
+            render::render_site(&toml_path, &out)?;
+        }
+    }
+    Ok(())
 }
@@ -0,0 +1,137 @@
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::fs;
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::link::Link;
+
+/// TOML front-matter block that may sit at the top of a Markdown notes file,
+/// fenced by `+++`. Only `title` is required; everything else is optional so a
+/// sparsely-annotated note still imports.
+#[derive(Debug, Deserialize)]
+struct FrontMatter {
+    title: String,
+    #[serde(default)]
+    link: Option<String>,
+    #[serde(default)]
+    date: Option<toml::value::Datetime>,
+    #[serde(default)]
+    updated: Option<toml::value::Datetime>,
+    #[serde(default)]
+    tags: Option<Vec<String>>,
+    #[serde(default)]
+    aliases: Option<Vec<String>>,
+    #[serde(default)]
+    draft: bool,
+}
+
+/// Split a file into its `+++`-fenced TOML front matter (group 1) and the
+/// remaining Markdown body (group 2). Returns `None` when there is no block.
+fn split_front_matter(content: &str) -> Option<(String, String)> {
+    // Group 1 captures the fenced TOML, group 2 the Markdown body that follows.
+    static FRONT_MATTER: OnceLock<Regex> = OnceLock::new();
+    let re = FRONT_MATTER.get_or_init(|| {
+        Regex::new(r"^\s*\+{3}(\r?\n(?s).*?(?-s))\+{3}\s*(?:$|\r?\n((?s).*(?-s))$)")
+            .expect("front-matter regex is a valid pattern")
+    });
+    let caps = re.captures(content)?;
+    let toml = caps.get(1)?.as_str().to_string();
+    let body = caps.get(2).map(|m| m.as_str()).unwrap_or("").to_string();
+    Some((toml, body))
+}
+
+/// The first non-empty paragraph of a Markdown body, used as the imported
+/// link's description.
+fn first_paragraph(body: &str) -> String {
+    body.split("\n\n")
+        .map(str::trim)
+        .find(|para| !para.is_empty())
+        .unwrap_or("")
+        .to_string()
+}
+
+/// Render the `YYYY-MM-DD` date component of a TOML datetime, dropping any time
+/// or offset so [`Link::new`]'s `"%Y-%m-%d"` parse succeeds. A time-only value
+/// (no date component) yields an empty string, falling back to today.
+fn date_str(dt: &toml::value::Datetime) -> String {
+    dt.date
+        .map(|d| format!("{:04}-{:02}-{:02}", d.year, d.month, d.day))
+        .unwrap_or_default()
+}
+
+/// Turn one front-matter block plus Markdown body into a [`Link`].
+fn to_link(front: FrontMatter, body: &str) -> Link {
+    let added = front.date.as_ref().map(date_str).unwrap_or_default();
+    let accessed = front.updated.as_ref().map(date_str).unwrap_or_default();
+    let link = front.link.unwrap_or_default();
+    let desc = first_paragraph(body);
+    let tags = front
+        .tags
+        .map(|tags| tags.into_iter().collect::<HashSet<String>>());
+    let _ = front.aliases;
+    Link::new(&front.title, &link, &desc, &added, &accessed, &tags, front.draft)
+}
+
+/// Read every `*.md` file in `dir`, parse its `+++` TOML front matter, and turn
+/// each into a [`Link`]. Files without a front-matter block are skipped with a
+/// warning rather than panicking, matching the tolerant style of validation.
+pub fn read_links_from_markdown(dir: &str) -> std::io::Result<Vec<Link>> {
+    let entries = fs::read_dir(dir)?;
+    let mut links = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("md") {
+            let content = match fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(err) => {
+                    warn!("Skipping {}: unable to read ({})", path.display(), err);
+                    continue;
+                }
+            };
+            let (toml, body) = match split_front_matter(&content) {
+                Some(split) => split,
+                None => {
+                    warn!("Skipping {}: no +++ front-matter block", path.display());
+                    continue;
+                }
+            };
+            match toml::from_str::<FrontMatter>(&toml) {
+                Ok(front) => links.push(to_link(front, &body)),
+                Err(err) => warn!("Skipping {}: unable to parse front matter ({})", path.display(), err),
+            }
+        }
+    }
+    info!("{} link(s) imported from Markdown.", links.len());
+    Ok(links)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_front_matter_from_body() {
+        let doc = "+++\ntitle = \"Hello\"\n+++\nbody text\n";
+        let (toml, body) = split_front_matter(doc).expect("has front matter");
+        assert_eq!(toml.trim(), "title = \"Hello\"");
+        assert_eq!(body.trim(), "body text");
+    }
+
+    #[test]
+    fn missing_front_matter_is_none() {
+        assert!(split_front_matter("no fence here\n").is_none());
+    }
+
+    #[test]
+    fn date_str_drops_time_and_offset() {
+        let dt: toml::value::Datetime = "2024-01-02T10:00:00Z".parse().unwrap();
+        assert_eq!(date_str(&dt), "2024-01-02");
+        let date_only: toml::value::Datetime = "2024-01-02".parse().unwrap();
+        assert_eq!(date_str(&date_only), "2024-01-02");
+    }
+}
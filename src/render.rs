@@ -0,0 +1,407 @@
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::link::Link;
+use crate::slugify;
+
+/// Numeric identifier assigned to each `Link` as it is collected, used as the
+/// key into the search index's postings and doc-record array.
+type DocId = usize;
+
+/// How much a token match in a given field contributes to a document's score.
+/// Title hits matter most, then tags, then the description body.
+const WEIGHT_TITLE: u32 = 8;
+const WEIGHT_TAGS: u32 = 4;
+const WEIGHT_DESC: u32 = 1;
+
+/// A single posting: the document a token was found in and the weight of the
+/// field it was found in. Multiple postings for the same `(token, doc)` pair are
+/// kept so repeated hits accumulate weight on the client.
+#[derive(Serialize)]
+struct Posting {
+    doc: DocId,
+    weight: u32,
+}
+
+/// The slim per-document record shipped to the browser so a search hit can be
+/// rendered without re-fetching anything.
+#[derive(Serialize)]
+struct DocRecord {
+    title: String,
+    link: String,
+    desc: String,
+}
+
+/// The payload serialized to `searchindex.json`: an inverted index from token to
+/// postings, plus a parallel array of doc records indexed by [`DocId`].
+#[derive(Serialize)]
+struct SearchIndex {
+    postings: HashMap<String, Vec<Posting>>,
+    docs: Vec<DocRecord>,
+}
+
+/// Lowercase `text`, split on whitespace and punctuation, and drop empty tokens.
+/// The shipped JS performs the identical transform on the query so the two token
+/// streams line up.
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|tok| !tok.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Walk `dir` and deserialize every `*.toml` into a [`Link`]. Unreadable or
+/// unparseable files are skipped with a warning rather than aborting the render.
+fn collect_links(dir: &str) -> std::io::Result<Vec<Link>> {
+    let entries = fs::read_dir(dir)?;
+    let mut links = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
+            let content = match fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(err) => {
+                    warn!("Skipping {}: unable to read ({})", path.display(), err);
+                    continue;
+                }
+            };
+            match toml::from_str::<Link>(&content) {
+                Ok(link) => links.push(link),
+                Err(err) => warn!("Skipping {}: unable to parse ({})", path.display(), err),
+            }
+        }
+    }
+    Ok(links)
+}
+
+/// HTML-escape the handful of characters that would otherwise break out of text
+/// or attribute context.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Return an escaped href only for schemes that cannot execute script
+/// (http/https/mailto or a relative/fragment link); anything else — e.g.
+/// `javascript:`/`data:` — becomes an inert `#`. Mirrors `safeHref` in the
+/// shipped `search.js` so server- and client-rendered links are hardened alike.
+fn safe_href(url: &str) -> String {
+    let lower = url.trim_start().to_lowercase();
+    if lower.starts_with("http:")
+        || lower.starts_with("https:")
+        || lower.starts_with("mailto:")
+        || url.starts_with('/')
+        || url.starts_with('#')
+    {
+        escape(url)
+    } else {
+        "#".to_string()
+    }
+}
+
+/// Build the inverted index and doc-record array over every collected link.
+fn build_index(links: &[Link]) -> SearchIndex {
+    let mut postings: HashMap<String, Vec<Posting>> = HashMap::new();
+    let mut docs = Vec::with_capacity(links.len());
+
+    for (doc, link) in links.iter().enumerate() {
+        let mut push = |text: &str, weight: u32| {
+            for token in tokenize(text) {
+                postings
+                    .entry(token)
+                    .or_default()
+                    .push(Posting { doc, weight });
+            }
+        };
+        push(&link.title, WEIGHT_TITLE);
+        if let Some(tags) = &link.tags {
+            for tag in tags {
+                push(tag, WEIGHT_TAGS);
+            }
+        }
+        push(&link.desc, WEIGHT_DESC);
+
+        docs.push(DocRecord {
+            title: link.title.clone(),
+            link: link.link.clone(),
+            desc: link.desc.clone(),
+        });
+    }
+
+    SearchIndex { postings, docs }
+}
+
+/// Render the per-bookmark detail page for a single link.
+fn render_detail(link: &Link) -> String {
+    let tags = link
+        .tags
+        .as_ref()
+        .map(|tags| {
+            let mut tags: Vec<&str> = tags.iter().map(String::as_ref).collect();
+            tags.sort_unstable();
+            tags.iter()
+                .map(|tag| format!("<li>{}</li>", escape(tag)))
+                .collect::<String>()
+        })
+        .unwrap_or_default();
+
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head><meta charset=\"utf-8\"><title>{title}</title></head>
+<body>
+<h1>{title}</h1>
+<p><a href=\"{href}\">{link}</a></p>
+<p>{desc}</p>
+<p>Added: {added} &middot; Last accessed: {accessed}</p>
+<ul>{tags}</ul>
+<p><a href=\"index.html\">&larr; Back to index</a></p>
+</body>
+</html>
+",
+        title = escape(&link.title),
+        href = safe_href(&link.link),
+        link = escape(&link.link),
+        desc = escape(&link.desc),
+        added = link.added,
+        accessed = link.accessed,
+        tags = tags,
+    )
+}
+
+/// Render the index page: every bookmark grouped by its first tag, plus the
+/// search box wired to `searchindex.json` and `search.js`.
+fn render_index(links: &[Link]) -> String {
+    let mut groups: HashMap<String, Vec<&Link>> = HashMap::new();
+    for link in links {
+        let tag = link
+            .tags
+            .as_ref()
+            .and_then(|tags| {
+                let mut tags: Vec<&str> = tags.iter().map(String::as_ref).collect();
+                tags.sort_unstable();
+                tags.first().map(|tag| tag.to_string())
+            })
+            .unwrap_or_else(|| "untagged".to_string());
+        groups.entry(tag).or_default().push(link);
+    }
+
+    let mut tags: Vec<&String> = groups.keys().collect();
+    tags.sort_unstable();
+
+    let mut body = String::new();
+    for tag in tags {
+        if tag == "untagged" {
+            body += "<h2>untagged</h2>\n<ul>\n";
+        } else {
+            body += &format!(
+                "<h2><a href=\"tag_{slug}.html\">{tag}</a></h2>\n<ul>\n",
+                slug = slugify(tag),
+                tag = escape(tag),
+            );
+        }
+        for link in &groups[tag] {
+            body += &format!(
+                "<li><a href=\"{slug}.html\">{title}</a></li>\n",
+                slug = slugify(&link.title),
+                title = escape(&link.title),
+            );
+        }
+        body += "</ul>\n";
+    }
+
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head><meta charset=\"utf-8\"><title>Bookmarks</title></head>
+<body>
+<h1>Bookmarks</h1>
+<input id=\"q\" type=\"search\" placeholder=\"Search...\" autocomplete=\"off\">
+<ul id=\"results\"></ul>
+<div id=\"browse\">
+{body}</div>
+<script src=\"search.js\"></script>
+</body>
+</html>
+",
+        body = body,
+    )
+}
+
+/// The client-side search box: tokenizes the query exactly as [`tokenize`] does,
+/// unions the postings, and ranks documents by summed field weight times match count.
+const SEARCH_JS: &str = r##"(async function () {
+  const index = await fetch("searchindex.json").then((r) => r.json());
+  const q = document.getElementById("q");
+  const results = document.getElementById("results");
+  const browse = document.getElementById("browse");
+
+  function tokenize(text) {
+    return text
+      .toLowerCase()
+      .split(/[^\p{Alphabetic}\p{N}]+/u)
+      .filter((tok) => tok.length > 0);
+  }
+
+  function escapeHtml(text) {
+    return text
+      .replace(/&/g, "&amp;")
+      .replace(/</g, "&lt;")
+      .replace(/>/g, "&gt;")
+      .replace(/"/g, "&quot;");
+  }
+
+  // Only allow hrefs that can't execute script; anything else becomes inert.
+  function safeHref(url) {
+    return /^(https?:|mailto:|\/|#)/i.test(url) ? escapeHtml(url) : "#";
+  }
+
+  function search(query) {
+    const tokens = tokenize(query);
+    const scores = new Map();
+    for (const token of tokens) {
+      const postings = index.postings[token];
+      if (!postings) continue;
+      for (const { doc, weight } of postings) {
+        scores.set(doc, (scores.get(doc) || 0) + weight);
+      }
+    }
+    return [...scores.entries()]
+      .sort((a, b) => b[1] - a[1])
+      .map(([doc]) => index.docs[doc]);
+  }
+
+  q.addEventListener("input", () => {
+    const query = q.value.trim();
+    if (query === "") {
+      results.innerHTML = "";
+      browse.style.display = "";
+      return;
+    }
+    browse.style.display = "none";
+    results.innerHTML = search(query)
+      .map(
+        (d) =>
+          `<li><a href="${safeHref(d.link)}">${escapeHtml(
+            d.title
+          )}</a><br><small>${escapeHtml(d.desc)}</small></li>`
+      )
+      .join("");
+  });
+})();
+"##;
+
+/// Invert `links` into a map from tag to the entries carrying it, with each
+/// tag's entries sorted by `added` descending (most recent first). Untagged
+/// entries do not appear.
+fn taxonomy(links: &[Link]) -> HashMap<String, Vec<&Link>> {
+    let mut by_tag: HashMap<String, Vec<&Link>> = HashMap::new();
+    for link in links {
+        if let Some(tags) = &link.tags {
+            for tag in tags {
+                by_tag.entry(tag.clone()).or_default().push(link);
+            }
+        }
+    }
+    for entries in by_tag.values_mut() {
+        entries.sort_by_key(|b| std::cmp::Reverse(b.added));
+    }
+    by_tag
+}
+
+/// Render one listing page per tag, each linking its entries newest-first.
+fn render_tag_page(tag: &str, entries: &[&Link]) -> String {
+    let mut items = String::new();
+    for link in entries {
+        items += &format!(
+            "<li><a href=\"{slug}.html\">{title}</a> <small>{added}</small></li>\n",
+            slug = slugify(&link.title),
+            title = escape(&link.title),
+            added = link.added,
+        );
+    }
+    format!(
+        "<!DOCTYPE html>
+<html lang=\"en\">
+<head><meta charset=\"utf-8\"><title>Tag: {tag}</title></head>
+<body>
+<h1>Tag: {tag}</h1>
+<ul>
+{items}</ul>
+<p><a href=\"index.html\">&larr; Back to index</a></p>
+</body>
+</html>
+",
+        tag = escape(tag),
+        items = items,
+    )
+}
+
+/// Render the whole collection in `dir` as a static site under `out`: an index
+/// page grouped by first tag, one detail page per bookmark, a per-tag taxonomy
+/// listing, `searchindex.json`, and the `search.js` search box. Draft and
+/// not-yet-published entries (see [`Link::is_published`]) are excluded.
+pub fn render_site(dir: &str, out: &Path) -> std::io::Result<()> {
+    let links: Vec<Link> = collect_links(dir)?
+        .into_iter()
+        .filter(Link::is_published)
+        .collect();
+    info!("Rendering {} bookmark(s) to {}.", links.len(), out.display());
+
+    if fs::metadata(out).is_err() {
+        fs::create_dir_all(out)?;
+    }
+
+    for link in &links {
+        let path = out.join(format!("{}.html", slugify(&link.title)));
+        fs::write(path, render_detail(link))?;
+    }
+
+    for (tag, entries) in taxonomy(&links) {
+        let path = out.join(format!("tag_{}.html", slugify(&tag)));
+        fs::write(path, render_tag_page(&tag, &entries))?;
+    }
+
+    fs::write(out.join("index.html"), render_index(&links))?;
+    fs::write(out.join("search.js"), SEARCH_JS)?;
+
+    let index = build_index(&links);
+    let json = serde_json::to_string(&index).expect("Unable to serialize search index");
+    fs::write(out.join("searchindex.json"), json)?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokenize_lowercases_and_splits_on_punctuation() {
+        assert_eq!(tokenize("Café foo!bar"), vec!["café", "foo", "bar"]);
+        assert!(tokenize("  ,, ").is_empty());
+    }
+
+    #[test]
+    fn build_index_weights_title_over_description() {
+        let tags = Some(["rust".to_string()].into_iter().collect());
+        let link = Link::new("Rust book", "https://x", "a rust guide", "", "", &tags, false);
+        let index = build_index(&[link]);
+
+        // "rust" appears in the title (8), a tag (4) and the description (1).
+        let rust: u32 = index.postings["rust"].iter().map(|p| p.weight).sum();
+        assert_eq!(rust, WEIGHT_TITLE + WEIGHT_TAGS + WEIGHT_DESC);
+        // "guide" only appears in the description.
+        assert_eq!(index.postings["guide"][0].weight, WEIGHT_DESC);
+        assert_eq!(index.docs.len(), 1);
+    }
+}
@@ -1,10 +1,13 @@
-use core::panic;
+use std::io::{Error, ErrorKind};
 use std::{ffi::OsStr, fs};
 
+use tracing::{error, info, warn};
+
 use crate::link::Link;
 
 fn validate_entry(content: &str) -> Result<(), String> {
-    let entry: Link = toml::from_str(content).expect("Unable to parse toml");
+    let entry: Link =
+        toml::from_str(content).map_err(|err| format!("Unable to parse toml: {}", err))?;
     if entry.title.is_empty() {
         return Err("Title is empty".to_string());
     }
@@ -13,28 +16,41 @@ fn validate_entry(content: &str) -> Result<(), String> {
     }
     if entry.desc.is_empty() {
         // Don't throw error, but alert.
-        print!("Contains no description.");
+        warn!("Contains no description.");
     }
     Ok(())
 }
 
 /// Validates all entries in the given directory, in relation to schema defined by Link struct.
+///
+/// A malformed or invalid entry is logged and counted rather than aborting the
+/// run; if any entry failed, the whole call returns an [`Err`] so the `validate`
+/// command exits nonzero.
 pub fn validate_entries(dir: &str) -> std::io::Result<()> {
-    let mut entries = fs::read_dir(dir)?;
+    let entries = fs::read_dir(dir)?;
     let mut i = 1;
+    let mut invalid = 0;
     for entry in entries {
         let entry = entry?;
         let path = entry.path();
         if path.is_file() && path.extension().and_then(OsStr::to_str) == Some("toml") {
-            println!("File {}: validating {}... ", i, path.display());
+            info!("File {}: validating {}... ", i, path.display());
             let content = fs::read_to_string(&path)?;
-            let result = validate_entry(&content);
-            match (result) {
-                Ok(()) => println!("\tIt's valid"),
-                Err(err) => panic!("{} is invalid: {}", path.display(), err),
+            match validate_entry(&content) {
+                Ok(()) => info!("\tIt's valid"),
+                Err(err) => {
+                    error!("{} is invalid: {}", path.display(), err);
+                    invalid += 1;
+                }
             }
             i += 1;
         }
     }
+    if invalid > 0 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("{} invalid entr{}", invalid, if invalid == 1 { "y" } else { "ies" }),
+        ));
+    }
     Ok(())
 }